@@ -8,11 +8,9 @@ fn unfold_sqrt(n: f64) -> Option<f64> {
         Some(n)
     } else {
         // this is Newton's  method to find root in non linear function
-        // applied to the function  f(x) = x² - n. 
+        // applied to the function  f(x) = x² - n.
         // The positive root, the one found by this function, is the square root of n
-        unfold::unfold_count(|x| ((x * x) + n) / (2.0 * x), n, 100)
-            .take_while(|x| ((x * x) - n).abs() > 1e-8)
-            .last()
+        unfold::unfold_fixpoint_eps(|x| ((x * x) + n) / (2.0 * x), n, 1e-8, 100).last()
     }
 }
 
@@ -30,9 +28,9 @@ mod test {
         for i in 0..20 {
             let n = i as f64;
             let res = unfold_sqrt(n * n).unwrap();
-            // Newton's method has a quadratic convergence
-            // so  the last itation with an error larger then
-            // 1e-8 has an error in the order of 1e-4
+            // unfold_fixpoint_eps stops once two successive guesses are
+            // within 1e-8 of each other, so the result is well within
+            // 1e-4 of the actual root.
             assert!((res - n).abs() < 1e-4);
         }
     }
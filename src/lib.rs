@@ -11,6 +11,11 @@
 //! that implements the unfold function as an *endless*
 //! iterator.
 //!
+//! For unfold sequences that should stop on their own,
+//! ```UnfoldState``` keeps a separate state value and calls a
+//! closure that returns an ```Option```, ending the iterator the
+//! first time that closure returns ```None```.
+//!
 //!
 //! ## Quick Start
 //!
@@ -33,6 +38,17 @@
 //! assert_eq!(vec![0, 1, 1, 2, 3], fibonacci_numbers);
 //! ```
 //!
+//! ```Unfold``` only requires the state to implement ```Clone```, so it
+//! also works with heap-allocated, non-```Copy``` types such as
+//! ```String``` or ```Vec```:
+//!
+//! ```
+//! use unfold::unfold_nth;
+//!
+//! let greeting = unfold_nth(|s: String| s + "a", String::from("h"), 5);
+//! assert_eq!(greeting, "haaaa");
+//! ```
+//!
 //!
 
 ///
@@ -42,7 +58,7 @@
 pub fn unfold<T, F>(func: F, init: T) -> Unfold<T, F>
 where
     F: Fn(T) -> T,
-    T: Copy,
+    T: Clone,
 {
     Unfold::new(func, init)
 }
@@ -60,7 +76,7 @@ where
 pub fn unfold_vector<T, F>(func: F, init: T, len: usize) -> Vec<T>
 where
     F: Fn(T) -> T,
-    T: Copy,
+    T: Clone,
 {
     unfold(func, init).take(len).collect()
 }
@@ -77,7 +93,7 @@ where
 pub fn unfold_nth<T, F>(func: F, init: T, index: usize) -> T
 where
     F: Fn(T) -> T,
-    T: Copy,
+    T: Clone,
 {
     unfold(func, init).take(index).last().unwrap()
 }
@@ -98,16 +114,264 @@ where
 pub fn unfold_count<T, F>(func: F, init: T, count: usize) -> impl Iterator<Item = T>
 where
     F: Fn(T) -> T,
-    T: Copy,
+    T: Clone,
 {
     unfold(func, init).take(count)
 }
 
+/// This function creates an unfold iterator that terminates as soon
+/// as two successive values satisfy `is_done`, i.e. the sequence has
+/// converged to a fixed point.
+///
+/// The returned iterator yields `[init, func(init), func(func(init)),
+/// ...]`, stopping right after the first value for which
+/// `is_done(&previous, &current)` is `true`. `max_iter` bounds how
+/// many values can ever be produced, guarding against a predicate
+/// that never converges.
+///
+/// ```
+/// use unfold::unfold_fixpoint;
+///
+/// // Newton's method for the square root of 100, stopping once two
+/// // successive guesses are within 1e-8 of each other.
+/// let n = 100.0;
+/// let sqrt_n = unfold_fixpoint(
+///     |x: f64| (x + n / x) / 2.0,
+///     n,
+///     |prev, curr| (curr - prev).abs() <= 1e-8,
+///     100,
+/// )
+/// .last()
+/// .unwrap();
+/// assert!((sqrt_n - 10.0).abs() < 1e-8);
+/// ```
+pub fn unfold_fixpoint<T, F, D>(
+    func: F,
+    init: T,
+    is_done: D,
+    max_iter: usize,
+) -> impl Iterator<Item = T>
+where
+    F: Fn(T) -> T,
+    D: Fn(&T, &T) -> bool,
+    T: Clone,
+{
+    struct State<T> {
+        prev: T,
+        started: bool,
+        stopped: bool,
+        iterations: usize,
+    }
+
+    let state = State {
+        prev: init,
+        started: false,
+        stopped: false,
+        iterations: 0,
+    };
+
+    unfold_state(state, move |state| {
+        if state.stopped {
+            return None;
+        }
+        if state.iterations >= max_iter {
+            state.stopped = true;
+            return None;
+        }
+        if !state.started {
+            state.started = true;
+            state.iterations = 1;
+            return Some(state.prev.clone());
+        }
+        let next = func(state.prev.clone());
+        state.iterations += 1;
+        if is_done(&state.prev, &next) {
+            state.stopped = true;
+        }
+        state.prev = next.clone();
+        Some(next)
+    })
+}
+
+/// This function is a convenience front-end to
+/// [`unfold_fixpoint`] for floating point sequences: it stops once
+/// two successive values are within `eps` of each other, capping the
+/// sequence at `max_iter` values in case it never converges.
+///
+/// ```
+/// use unfold::unfold_fixpoint_eps;
+///
+/// let n = 100.0;
+/// let sqrt_n = unfold_fixpoint_eps(|x: f64| (x + n / x) / 2.0, n, 1e-8, 100)
+///     .last()
+///     .unwrap();
+/// assert!((sqrt_n - 10.0).abs() < 1e-8);
+/// ```
+pub fn unfold_fixpoint_eps<T, F>(
+    func: F,
+    init: T,
+    eps: T,
+    max_iter: usize,
+) -> impl Iterator<Item = T>
+where
+    F: Fn(T) -> T,
+    T: Clone + PartialOrd + std::ops::Sub<Output = T>,
+{
+    unfold_fixpoint(
+        func,
+        init,
+        move |prev, curr| {
+            let diff = if curr >= prev {
+                curr.clone() - prev.clone()
+            } else {
+                prev.clone() - curr.clone()
+            };
+            diff <= eps
+        },
+        max_iter,
+    )
+}
+
+/// This function is a simple front-end to
+/// UnfoldState::new: allows the user to easily create a
+/// new, terminating UnfoldState iterator
+///
+/// ```
+/// use unfold::unfold_state;
+///
+/// // Unlike Unfold, the state (a, b) and the yielded item (a single
+/// // u64) are different types, and the iterator stops on its own.
+/// let fibonacci_numbers: Vec<u64> = unfold_state((0u64, 1u64), |state| {
+///     let (a, b) = *state;
+///     if a > 10 {
+///         None
+///     } else {
+///         *state = (b, a + b);
+///         Some(a)
+///     }
+/// })
+/// .collect();
+///
+/// assert_eq!(vec![0, 1, 1, 2, 3, 5, 8], fibonacci_numbers);
+/// ```
+pub fn unfold_state<St, A, F>(state: St, f: F) -> UnfoldState<St, F>
+where
+    F: FnMut(&mut St) -> Option<A>,
+{
+    UnfoldState::new(state, f)
+}
+
+/// Define a stateful, terminating unfold iterator.
+///
+/// Unlike [`Unfold`], which never stops and requires the yielded item
+/// to be the same type as the internal state, `UnfoldState` keeps a
+/// separate `state: St` and calls a closure
+/// `F: FnMut(&mut St) -> Option<A>` to produce each item: the iterator
+/// ends the first time the closure returns `None`. This allows the
+/// closure to mutate or drain the state in place (e.g. popping from a
+/// collection) and to yield an item type unrelated to the state type.
+pub struct UnfoldState<St, F> {
+    state: St,
+    f: F,
+}
+
+impl<St, F> UnfoldState<St, F> {
+    ///
+    ///Create a new UnfoldState instance
+    ///```
+    /// use unfold::UnfoldState;
+    ///
+    /// // drain a vector, yielding its items in reverse order
+    /// let mut source = UnfoldState::new(vec![1, 2, 3], |state: &mut Vec<i32>| state.pop());
+    /// assert_eq!(source.next(), Some(3));
+    /// assert_eq!(source.next(), Some(2));
+    /// assert_eq!(source.next(), Some(1));
+    /// assert_eq!(source.next(), None);
+    ///```
+    pub fn new(state: St, f: F) -> Self {
+        Self { state, f }
+    }
+}
+
+impl<St, A, F> Iterator for UnfoldState<St, F>
+where
+    F: FnMut(&mut St) -> Option<A>,
+{
+    type Item = A;
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.f)(&mut self.state)
+    }
+}
+
+/// This function creates an unfold iterator from a fallible generator
+/// `func`, yielding the inner value as long as `func` produces `Some`
+/// and stopping at the first `None`. This is the unfold-flavoured
+/// counterpart of the itertools idiom
+/// `repeat_call(|| heap.pop()).while_some()`.
+///
+/// ```
+/// use std::collections::BinaryHeap;
+/// use unfold::unfold_while_some;
+///
+/// let heap: BinaryHeap<i32> = vec![3, 1, 4, 1, 5].into_iter().collect();
+/// let sorted: Vec<i32> = unfold_while_some(|heap: &mut BinaryHeap<i32>| heap.pop(), heap).collect();
+/// assert_eq!(sorted, vec![5, 4, 3, 1, 1]);
+/// ```
+pub fn unfold_while_some<St, T, F>(mut func: F, init: St) -> impl Iterator<Item = T>
+where
+    F: FnMut(&mut St) -> Option<T>,
+{
+    unfold_state(init, move |state| Some(func(state))).while_some()
+}
+
+/// Extension trait adding [`while_some`](WhileSomeExt::while_some) to
+/// any iterator of `Option`s.
+pub trait WhileSomeExt: Iterator {
+    /// Turn an iterator of `Option<T>` into an iterator of `T` that
+    /// stops as soon as it sees the first `None`.
+    fn while_some<T>(self) -> WhileSome<Self>
+    where
+        Self: Iterator<Item = Option<T>> + Sized,
+    {
+        WhileSome {
+            inner: self,
+            done: false,
+        }
+    }
+}
+
+impl<I: Iterator> WhileSomeExt for I {}
+
+/// Iterator returned by [`WhileSomeExt::while_some`]
+pub struct WhileSome<I> {
+    inner: I,
+    done: bool,
+}
+
+impl<I, T> Iterator for WhileSome<I>
+where
+    I: Iterator<Item = Option<T>>,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.inner.next() {
+            Some(Some(item)) => Some(item),
+            _ => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
 /// Define an endless unfold iterator
 pub struct Unfold<T, F>
 where
     F: Fn(T) -> T,
-    T: Copy,
+    T: Clone,
 {
     curr: T,
     func: F,
@@ -116,7 +380,7 @@ where
 impl<T, F> Unfold<T, F>
 where
     F: Fn(T) -> T,
-    T: Copy,
+    T: Clone,
 {
     ///
     ///Create a new Unfold instance
@@ -137,13 +401,40 @@ where
 impl<T, F> Iterator for Unfold<T, F>
 where
     F: Fn(T) -> T,
-    T: Copy,
+    T: Clone,
 {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
-        let tmp = self.curr;
-        self.curr = (self.func)(self.curr);
-        Some(tmp)
+        let next = (self.func)(self.curr.clone());
+        Some(std::mem::replace(&mut self.curr, next))
+    }
+
+    /// Unfold never ends, so it always reports an unbounded lower
+    /// bound and no upper bound.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+
+    /// Drive the accumulation loop internally instead of going
+    /// through the default `next()`-based implementation, avoiding an
+    /// `Option` wrap/unwrap per produced item.
+    ///
+    /// `try_fold` itself is not overridden: its default signature is
+    /// bound on `std::ops::Try`, which is still an unstable library
+    /// feature, so it can't be named from this crate. Short-circuiting
+    /// adaptors such as `find` still work correctly, just through the
+    /// `Iterator`-provided default rather than this faster internal
+    /// loop.
+    fn fold<B, G>(mut self, init: B, mut g: G) -> B
+    where
+        G: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = init;
+        loop {
+            let next = (self.func)(self.curr.clone());
+            let item = std::mem::replace(&mut self.curr, next);
+            acc = g(acc, item);
+        }
     }
 }
 
@@ -175,6 +466,105 @@ mod tests {
         assert_eq!(count, result);
     }
 
+    #[test]
+    fn test_unfold_while_some_drains_heap() {
+        use std::collections::BinaryHeap;
+
+        let heap: BinaryHeap<i32> = vec![3, 1, 4, 1, 5].into_iter().collect();
+        let sorted: Vec<i32> =
+            unfold_while_some(|heap: &mut BinaryHeap<i32>| heap.pop(), heap).collect();
+        assert_eq!(sorted, vec![5, 4, 3, 1, 1]);
+    }
+
+    #[test]
+    fn test_while_some_stops_at_first_none() {
+        let values = vec![Some(1), Some(2), None, Some(3)];
+        let taken: Vec<i32> = values.into_iter().while_some().collect();
+        assert_eq!(taken, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_while_some_is_fused() {
+        let values = vec![Some(1), Some(2), None, Some(3)];
+        let mut iter = values.into_iter().while_some();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_unfold_fixpoint_sqrt() {
+        let n = 100.0;
+        let sqrt_n = unfold_fixpoint(
+            |x: f64| (x + n / x) / 2.0,
+            n,
+            |prev, curr| (curr - prev).abs() <= 1e-8,
+            100,
+        )
+        .last()
+        .unwrap();
+        assert!((sqrt_n - 10.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_unfold_fixpoint_eps_sqrt() {
+        let n = 100.0;
+        let sqrt_n = unfold_fixpoint_eps(|x: f64| (x + n / x) / 2.0, n, 1e-8, 100)
+            .last()
+            .unwrap();
+        assert!((sqrt_n - 10.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_unfold_fixpoint_max_iter_caps_output() {
+        // never converges, so max_iter bounds the number of items
+        let values: Vec<i32> = unfold_fixpoint(|x: i32| x + 1, 0, |_, _| false, 5).collect();
+        assert_eq!(values, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_unfold_fixpoint_zero_max_iter_yields_nothing() {
+        let values: Vec<i32> = unfold_fixpoint(|x: i32| x + 1, 0, |_, _| false, 0).collect();
+        assert_eq!(values, Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_unfold_find_short_circuits() {
+        let first_over_50 = unfold(|x: u32| x + 1, 0).find(|&x| x > 50);
+        assert_eq!(first_over_50, Some(51));
+    }
+
+    #[test]
+    fn test_unfold_non_copy() {
+        let greeting = unfold_nth(|s: String| s + "a", String::from("h"), 5);
+        assert_eq!(greeting, "haaaa");
+    }
+
+    #[test]
+    fn test_unfold_state_fibonacci() {
+        let fib: Vec<u64> = unfold_state((0u64, 1u64), |state| {
+            let (a, b) = *state;
+            if a > 10 {
+                None
+            } else {
+                *state = (b, a + b);
+                Some(a)
+            }
+        })
+        .collect();
+        assert_eq!(fib, vec![0, 1, 1, 2, 3, 5, 8]);
+    }
+
+    #[test]
+    fn test_unfold_state_drain() {
+        let mut source = UnfoldState::new(vec![1, 2, 3], |state: &mut Vec<i32>| state.pop());
+        assert_eq!(source.next(), Some(3));
+        assert_eq!(source.next(), Some(2));
+        assert_eq!(source.next(), Some(1));
+        assert_eq!(source.next(), None);
+    }
+
     #[test]
     fn test_unfold_count() {
         let mut iter = unfold_count(|x| x + 1, 0, 5);